@@ -0,0 +1,123 @@
+//! Runtime configuration. Resolved in three layers, lowest precedence first:
+//! built-in defaults, `FKS_*` environment variables, then CLI flags (see
+//! `Cli` in `main.rs`) — so the service can move between accounts and
+//! environments without a recompile.
+
+use std::env;
+
+pub const DEFAULT_ACCOUNT_EQUITY: f64 = 150_000.0;
+pub const DEFAULT_RISK_FRACTION: f64 = 0.01;
+pub const DEFAULT_SYMBOL: &str = "ES";
+pub const DEFAULT_RSI_PERIOD: usize = 14;
+pub const DEFAULT_LISTEN: &str = "0.0.0.0:4700";
+pub const DEFAULT_MAX_CONTRACTS: u64 = 10;
+pub const DEFAULT_REWARD_RISK_MULTIPLE: f64 = 2.0;
+pub const DEFAULT_NEUTRAL_RSI_LOWER: f64 = 45.0;
+pub const DEFAULT_NEUTRAL_RSI_UPPER: f64 = 55.0;
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub account_equity: f64,
+    pub risk_fraction: f64,
+    pub default_symbol: String,
+    /// Overrides the built-in synthetic fallback series (see
+    /// `builtin_default_prices` in `main.rs`) when a caller doesn't supply
+    /// both `symbol` and non-empty `prices`. `None` means use the built-in
+    /// series.
+    pub default_prices: Option<Vec<f64>>,
+    pub rsi_period: usize,
+    pub listen: String,
+    pub market_data_sources: Vec<String>,
+    pub max_contracts: u64,
+    pub reward_risk_multiple: f64,
+    pub neutral_rsi_lower: f64,
+    pub neutral_rsi_upper: f64,
+}
+
+impl Config {
+    /// Reads `FKS_ACCOUNT_EQUITY`, `FKS_RISK_FRACTION`, `FKS_DEFAULT_SYMBOL`,
+    /// `FKS_DEFAULT_PRICES` (comma separated floats), `FKS_RSI_PERIOD`,
+    /// `FKS_LISTEN`, `FKS_MARKET_DATA_SOURCES` (comma separated `host:port`
+    /// entries), `FKS_MAX_CONTRACTS`, `FKS_REWARD_RISK_MULTIPLE`,
+    /// `FKS_NEUTRAL_RSI_LOWER` and `FKS_NEUTRAL_RSI_UPPER`, falling back to
+    /// defaults for any variable that is unset or fails to parse.
+    pub fn from_env() -> Self {
+        Config {
+            account_equity: env_parsed("FKS_ACCOUNT_EQUITY").unwrap_or(DEFAULT_ACCOUNT_EQUITY),
+            risk_fraction: env_parsed("FKS_RISK_FRACTION").unwrap_or(DEFAULT_RISK_FRACTION),
+            default_symbol: env::var("FKS_DEFAULT_SYMBOL").unwrap_or_else(|_| DEFAULT_SYMBOL.to_string()),
+            default_prices: env_prices("FKS_DEFAULT_PRICES"),
+            rsi_period: env_parsed("FKS_RSI_PERIOD").unwrap_or(DEFAULT_RSI_PERIOD),
+            listen: env::var("FKS_LISTEN").unwrap_or_else(|_| DEFAULT_LISTEN.to_string()),
+            market_data_sources: env::var("FKS_MARKET_DATA_SOURCES")
+                .map(|v| v.split(',').map(str::trim).filter(|s| !s.is_empty()).map(str::to_string).collect())
+                .unwrap_or_default(),
+            max_contracts: env_parsed("FKS_MAX_CONTRACTS").unwrap_or(DEFAULT_MAX_CONTRACTS),
+            reward_risk_multiple: env_parsed("FKS_REWARD_RISK_MULTIPLE").unwrap_or(DEFAULT_REWARD_RISK_MULTIPLE),
+            neutral_rsi_lower: env_parsed("FKS_NEUTRAL_RSI_LOWER").unwrap_or(DEFAULT_NEUTRAL_RSI_LOWER),
+            neutral_rsi_upper: env_parsed("FKS_NEUTRAL_RSI_UPPER").unwrap_or(DEFAULT_NEUTRAL_RSI_UPPER),
+        }
+    }
+}
+
+fn env_parsed<T: std::str::FromStr>(key: &str) -> Option<T> {
+    env::var(key).ok().and_then(|v| v.parse().ok())
+}
+
+/// Parses a comma-separated list of floats from `key`; `None` if unset,
+/// empty, or any entry fails to parse (never a partial list).
+fn env_prices(key: &str) -> Option<Vec<f64>> {
+    let raw = env::var(key).ok()?;
+    let parsed: Result<Vec<f64>, _> = raw.split(',').map(|s| s.trim().parse::<f64>()).collect();
+    parsed.ok().filter(|prices| !prices.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_equity_env_override_and_default_fallback() {
+        env::remove_var("FKS_ACCOUNT_EQUITY");
+        assert_eq!(Config::from_env().account_equity, DEFAULT_ACCOUNT_EQUITY);
+
+        env::set_var("FKS_ACCOUNT_EQUITY", "250000");
+        assert_eq!(Config::from_env().account_equity, 250_000.0);
+
+        env::remove_var("FKS_ACCOUNT_EQUITY");
+    }
+
+    #[test]
+    fn invalid_rsi_period_env_value_falls_back_to_default() {
+        env::set_var("FKS_RSI_PERIOD", "not-a-number");
+        assert_eq!(Config::from_env().rsi_period, DEFAULT_RSI_PERIOD);
+        env::remove_var("FKS_RSI_PERIOD");
+    }
+
+    #[test]
+    fn market_data_sources_parses_trimmed_comma_separated_list() {
+        env::set_var("FKS_MARKET_DATA_SOURCES", " a:1, b:2 ,,c:3");
+        assert_eq!(Config::from_env().market_data_sources, vec!["a:1", "b:2", "c:3"]);
+        env::remove_var("FKS_MARKET_DATA_SOURCES");
+    }
+
+    #[test]
+    fn default_prices_unset_is_none() {
+        env::remove_var("FKS_DEFAULT_PRICES");
+        assert_eq!(Config::from_env().default_prices, None);
+    }
+
+    #[test]
+    fn default_prices_parses_comma_separated_floats() {
+        env::set_var("FKS_DEFAULT_PRICES", "4420.0, 4422.5 ,4419.0");
+        assert_eq!(Config::from_env().default_prices, Some(vec![4420.0, 4422.5, 4419.0]));
+        env::remove_var("FKS_DEFAULT_PRICES");
+    }
+
+    #[test]
+    fn default_prices_with_unparseable_entry_falls_back_to_none() {
+        env::set_var("FKS_DEFAULT_PRICES", "4420.0, not-a-number");
+        assert_eq!(Config::from_env().default_prices, None);
+        env::remove_var("FKS_DEFAULT_PRICES");
+    }
+}
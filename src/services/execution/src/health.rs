@@ -0,0 +1,85 @@
+//! Readiness checks for `/ready`. `/health` stays a cheap liveness probe in
+//! `main.rs`; these run real work, so they're kept out of the hot path.
+
+use serde::Serialize;
+
+#[derive(Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+}
+
+#[derive(Serialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl CheckResult {
+    pub fn pass(name: &str, detail: Option<String>) -> Self {
+        CheckResult { name: name.to_string(), status: CheckStatus::Pass, detail }
+    }
+
+    pub fn fail(name: &str, detail: String) -> Self {
+        CheckResult { name: name.to_string(), status: CheckStatus::Fail, detail: Some(detail) }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Readiness {
+    pub status: &'static str,
+    pub checks: Vec<CheckResult>,
+}
+
+impl Readiness {
+    pub fn from_checks(checks: Vec<CheckResult>) -> Self {
+        let status = if checks.iter().all(|c| c.status == CheckStatus::Pass) { "ready" } else { "degraded" };
+        Readiness { status, checks }
+    }
+
+    pub fn all_pass(&self) -> bool {
+        self.checks.iter().all(|c| c.status == CheckStatus::Pass)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ready_when_every_check_passes() {
+        let readiness = Readiness::from_checks(vec![
+            CheckResult::pass("a", None),
+            CheckResult::pass("b", Some("ok".into())),
+        ]);
+        assert_eq!(readiness.status, "ready");
+        assert!(readiness.all_pass());
+    }
+
+    #[test]
+    fn degraded_when_any_check_fails() {
+        let readiness = Readiness::from_checks(vec![
+            CheckResult::pass("a", None),
+            CheckResult::fail("b", "down".into()),
+        ]);
+        assert_eq!(readiness.status, "degraded");
+        assert!(!readiness.all_pass());
+    }
+
+    #[test]
+    fn degraded_when_all_checks_fail() {
+        let readiness = Readiness::from_checks(vec![CheckResult::fail("a", "down".into())]);
+        assert_eq!(readiness.status, "degraded");
+        assert!(!readiness.all_pass());
+    }
+
+    #[test]
+    fn no_checks_is_vacuously_ready() {
+        let readiness = Readiness::from_checks(vec![]);
+        assert_eq!(readiness.status, "ready");
+        assert!(readiness.all_pass());
+    }
+}
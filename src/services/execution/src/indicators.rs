@@ -0,0 +1,185 @@
+//! RSI, EMA, MACD and Bollinger Band math over a raw price series. These
+//! implement the exact seeding/smoothing rules `build_signal` needs (Wilder
+//! smoothing for RSI, SMA-seeded EMA, …), so the behavior is verifiable here
+//! independent of the HTTP layer that calls into it.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy)]
+pub enum IndicatorError {
+    InsufficientData { required: usize, got: usize },
+}
+
+impl std::fmt::Display for IndicatorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IndicatorError::InsufficientData { required, got } => {
+                write!(f, "need at least {required} prices, got {got}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for IndicatorError {}
+
+/// Wilder's RSI over `period` bars (default 14). Seeds `avg_gain`/`avg_loss`
+/// as the simple mean of the first `period` deltas, then applies Wilder
+/// smoothing for the remainder. `avg_loss == 0` yields `RSI = 100`.
+pub fn rsi(prices: &[f64], period: usize) -> Result<f64, IndicatorError> {
+    if period == 0 || prices.len() < period + 1 {
+        return Err(IndicatorError::InsufficientData { required: period + 1, got: prices.len() });
+    }
+    let deltas: Vec<f64> = prices.windows(2).map(|w| w[1] - w[0]).collect();
+    let n = period as f64;
+    let mut avg_gain = deltas[..period].iter().map(|d| d.max(0.0)).sum::<f64>() / n;
+    let mut avg_loss = deltas[..period].iter().map(|d| (-d).max(0.0)).sum::<f64>() / n;
+    for d in &deltas[period..] {
+        avg_gain = (avg_gain * (n - 1.0) + d.max(0.0)) / n;
+        avg_loss = (avg_loss * (n - 1.0) + (-d).max(0.0)) / n;
+    }
+    if avg_loss == 0.0 {
+        return Ok(100.0);
+    }
+    let rs = avg_gain / avg_loss;
+    Ok(100.0 - 100.0 / (1.0 + rs))
+}
+
+/// Exponential moving average over `period` bars, seeded with the SMA of the
+/// first `period` prices: `k = 2/(n+1)`, `ema = p*k + ema_prev*(1-k)`.
+pub fn ema(prices: &[f64], period: usize) -> Result<f64, IndicatorError> {
+    Ok(*ema_series(prices, period)?.last().unwrap())
+}
+
+/// Full EMA series (one value per bar from `period - 1` onward), used
+/// internally by `ema` and `macd`.
+fn ema_series(prices: &[f64], period: usize) -> Result<Vec<f64>, IndicatorError> {
+    if period == 0 || prices.len() < period {
+        return Err(IndicatorError::InsufficientData { required: period, got: prices.len() });
+    }
+    let k = 2.0 / (period as f64 + 1.0);
+    let mut out = Vec::with_capacity(prices.len() - period + 1);
+    let mut prev = prices[..period].iter().sum::<f64>() / period as f64;
+    out.push(prev);
+    for p in &prices[period..] {
+        prev = p * k + prev * (1.0 - k);
+        out.push(prev);
+    }
+    Ok(out)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Macd {
+    pub macd: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+/// MACD: the difference between a fast and slow EMA (conventionally 12/26),
+/// smoothed again by a `signal_period`-bar EMA (conventionally 9).
+pub fn macd(prices: &[f64], fast: usize, slow: usize, signal_period: usize) -> Result<Macd, IndicatorError> {
+    if slow < fast {
+        return Err(IndicatorError::InsufficientData { required: fast, got: slow });
+    }
+    if prices.len() < slow + signal_period {
+        return Err(IndicatorError::InsufficientData { required: slow + signal_period, got: prices.len() });
+    }
+    let fast_series = ema_series(prices, fast)?;
+    let slow_series = ema_series(prices, slow)?;
+    let offset = fast_series.len() - slow_series.len();
+    let macd_series: Vec<f64> = slow_series
+        .iter()
+        .zip(&fast_series[offset..])
+        .map(|(slow, fast)| fast - slow)
+        .collect();
+    let signal = ema(&macd_series, signal_period)?;
+    let macd_val = *macd_series.last().unwrap();
+    Ok(Macd { macd: macd_val, signal, histogram: macd_val - signal })
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BollingerBands {
+    pub upper: f64,
+    pub middle: f64,
+    pub lower: f64,
+}
+
+/// Bollinger Bands: a `period`-bar SMA (`middle`) with upper/lower bands at
+/// `std_dev_mult` population standard deviations (conventionally 20, 2).
+pub fn bollinger_bands(prices: &[f64], period: usize, std_dev_mult: f64) -> Result<BollingerBands, IndicatorError> {
+    if period == 0 || prices.len() < period {
+        return Err(IndicatorError::InsufficientData { required: period, got: prices.len() });
+    }
+    let window = &prices[prices.len() - period..];
+    let mean = window.iter().sum::<f64>() / period as f64;
+    let variance = window.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / period as f64;
+    let std_dev = variance.sqrt();
+    Ok(BollingerBands {
+        upper: mean + std_dev_mult * std_dev,
+        middle: mean,
+        lower: mean - std_dev_mult * std_dev,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rsi_insufficient_data_errors() {
+        let prices = [1.0, 2.0, 3.0];
+        let err = rsi(&prices, 14).unwrap_err();
+        assert!(matches!(err, IndicatorError::InsufficientData { required: 15, got: 3 }));
+    }
+
+    #[test]
+    fn rsi_zero_avg_loss_is_100() {
+        // Monotonically increasing prices mean avg_loss stays zero throughout.
+        let prices: Vec<f64> = (0..20).map(|i| 100.0 + i as f64).collect();
+        assert_eq!(rsi(&prices, 14).unwrap(), 100.0);
+    }
+
+    #[test]
+    fn rsi_seeds_with_simple_mean_then_wilder_smooths() {
+        // period = 2 over 4 prices: one seeded bar (deltas +1,-1), one Wilder-smoothed bar (delta +2).
+        // seed avg_gain = 0.5, avg_loss = 0.5; smoothed avg_gain = 1.25, avg_loss = 0.25; rs = 5.
+        let prices = [10.0, 11.0, 10.0, 12.0];
+        let value = rsi(&prices, 2).unwrap();
+        assert!((value - (100.0 - 100.0 / 6.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ema_seeds_with_sma_of_first_period() {
+        // period = 3: seed = mean(1,2,3) = 2.0, k = 2/4 = 0.5, next = 10*0.5 + 2.0*0.5 = 6.0.
+        let prices = [1.0, 2.0, 3.0, 10.0];
+        assert_eq!(ema(&prices, 3).unwrap(), 6.0);
+    }
+
+    #[test]
+    fn ema_insufficient_data_errors() {
+        let prices = [1.0, 2.0];
+        assert!(ema(&prices, 5).is_err());
+    }
+
+    #[test]
+    fn macd_requires_slow_plus_signal_bars() {
+        let prices: Vec<f64> = (0..30).map(|i| i as f64).collect();
+        assert!(macd(&prices, 12, 26, 9).is_err());
+    }
+
+    #[test]
+    fn macd_histogram_is_macd_minus_signal() {
+        let prices: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 * 0.3).sin() * 5.0).collect();
+        let result = macd(&prices, 12, 26, 9).unwrap();
+        assert!((result.histogram - (result.macd - result.signal)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bollinger_bands_bracket_the_mean() {
+        let prices = [
+            10.0, 11.0, 9.0, 10.0, 12.0, 8.0, 10.0, 11.0, 9.0, 10.0,
+            11.0, 9.0, 10.0, 12.0, 8.0, 10.0, 11.0, 9.0, 10.0, 11.0,
+        ];
+        let bands = bollinger_bands(&prices, 20, 2.0).unwrap();
+        assert!(bands.lower < bands.middle && bands.middle < bands.upper);
+    }
+}
@@ -1,21 +1,96 @@
-use axum::{routing::{get, post}, Router, Json, extract::State};
+use axum::{
+    routing::{get, post}, Router, Json, extract::{State, Query},
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+};
 use clap::Parser;
 use serde::Serialize;
-use std::{net::SocketAddr, time::{Instant, Duration}, sync::Arc};
-use tokio::signal;
+use std::{convert::Infallible, net::SocketAddr, time::{Instant, Duration}, sync::Arc};
+use tokio::{signal, sync::broadcast};
+use tokio_stream::wrappers::BroadcastStream;
+use futures_util::{future::join_all, Stream, StreamExt};
 use serde::Deserialize;
 
+mod config;
+mod health;
+mod indicators;
+mod risk;
+use config::Config;
+use health::{CheckResult, Readiness};
+use indicators::{BollingerBands, Macd};
+use risk::SizedPosition;
+
+/// Synthetic series used to probe the indicator engine in `/ready`; long
+/// enough for the default RSI period regardless of configured overrides.
+const READINESS_CANARY_PRICES: [f64; 16] = [
+    100.0, 101.2, 100.8, 102.1, 103.0, 102.4, 104.1, 105.0,
+    104.6, 106.2, 107.0, 106.1, 108.3, 109.0, 108.4, 110.0,
+];
+const READINESS_RSI_PERIOD: usize = 10;
+
+const DEFAULT_EMA_PERIOD: usize = 10;
+const DEFAULT_MACD_FAST: usize = 12;
+const DEFAULT_MACD_SLOW: usize = 26;
+const DEFAULT_MACD_SIGNAL: usize = 9;
+const DEFAULT_BOLLINGER_PERIOD: usize = 20;
+const DEFAULT_BOLLINGER_STD_DEV: f64 = 2.0;
+
+/// CLI flags. Each is optional and, when set, overrides the corresponding
+/// `FKS_*` env var / default from `Config::from_env`.
 #[derive(Parser, Debug)]
-#[command(version, about="FKS Execution API")] struct Cli { #[arg(long, default_value="0.0.0.0:4700")] listen: String }
+#[command(version, about="FKS Execution API")]
+struct Cli {
+    #[arg(long)] listen: Option<String>,
+    #[arg(long)] account_equity: Option<f64>,
+    #[arg(long)] risk_fraction: Option<f64>,
+    #[arg(long)] default_symbol: Option<String>,
+    #[arg(long)] rsi_period: Option<usize>,
+}
 
-#[derive(Serialize, Clone)] struct Signal { symbol: String, rsi: f64, ema: f64, risk_allowance: f64, latency_ms: u128 }
+#[derive(Serialize, Clone)]
+struct Signal {
+    symbol: String,
+    rsi: f64,
+    ema: f64,
+    risk_allowance: f64,
+    latency_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    macd: Option<Macd>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bollinger: Option<BollingerBands>,
+}
 
-#[derive(Deserialize)] struct SignalRequest { symbol: Option<String>, prices: Option<Vec<f64>> }
+#[derive(Deserialize)]
+struct SignalRequest {
+    symbol: Option<String>,
+    prices: Option<Vec<f64>>,
+    rsi_period: Option<usize>,
+    ema_period: Option<usize>,
+    include_macd: Option<bool>,
+    include_bollinger: Option<bool>,
+}
 
 #[derive(Serialize)] struct Health { service: String, status: String }
 
+#[derive(Serialize)] struct ErrorBody { error: String }
+
+/// Capacity of the in-process signal broadcast channel; slow subscribers
+/// that fall this far behind the latest signal get a `Lagged` error and
+/// simply miss the skipped events rather than blocking publishers.
+const SIGNAL_BROADCAST_CAPACITY: usize = 256;
+
 #[derive(Clone)]
-struct AppState { start: Instant }
+struct AppState {
+    start: Instant,
+    signal_tx: broadcast::Sender<SignalEvent>,
+    config: Config,
+}
+
+#[derive(Serialize, Clone)]
+struct SignalEvent {
+    symbol: String,
+    signal: Signal,
+}
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -27,35 +102,90 @@ async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt::init();
     tracing::info!("startup_begin");
     let cli = Cli::parse();
-    tracing::info!(listen = %cli.listen, "parsed_cli");
-    let state = AppState { start: Instant::now() };
+    let config = resolve_config(cli);
+    tracing::info!(listen = %config.listen, "parsed_config");
+    let (signal_tx, _) = broadcast::channel(SIGNAL_BROADCAST_CAPACITY);
+    let listen = config.listen.clone();
+    let state = AppState { start: Instant::now(), signal_tx, config };
     let signal_routes = Router::new()
         .route("/execute/signal", get(get_signal_handler))
-        .route("/execute/signal", post(post_signal_handler));
+        .route("/execute/signal", post(post_signal_handler))
+        .route("/execute/stream", get(stream_handler))
+        .route("/execute/order", post(post_order_handler));
 
     let app = Router::new()
         .route("/health", get(health_handler))
+        .route("/ready", get(ready_handler))
         .merge(signal_routes)
         .with_state(Arc::new(state));
-    let addr: SocketAddr = match cli.listen.parse() { Ok(a) => a, Err(e) => { tracing::error!(error=%e, "addr_parse_failed"); return Err(e.into()); } };
+    let addr: SocketAddr = match listen.parse() { Ok(a) => a, Err(e) => { tracing::error!(error=%e, "addr_parse_failed"); return Err(e.into()); } };
     tracing::info!(%addr, "binding_listener");
     let listener = match tokio::net::TcpListener::bind(addr).await { Ok(l) => l, Err(e) => { tracing::error!(error=%e, "bind_failed"); return Err(e.into()); } };
     tracing::info!("listener_bound");
-    let server = axum::serve(listener, app);
-    tracing::info!("server_future_created");
-    tokio::select! {
-        res = server => {
-            if let Err(e) = res { tracing::error!(error=%e, "server_terminated_error"); }
-            tracing::warn!("server_future_completed_unexpectedly");
+    let result = axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await;
+    match result {
+        Ok(()) => {
+            tracing::info!("server_shut_down_cleanly");
+            Ok(())
         }
-        _ = shutdown_signal() => {
-            tracing::info!("shutdown signal received");
+        Err(e) => {
+            tracing::error!(error=%e, "server_terminated_error");
+            Err(e.into())
         }
     }
-    // If we get here the server ended unexpectedly; keep process alive for inspection
-    tracing::warn!("execution_main_exiting_loop_enter");
-    loop {
-        tokio::time::sleep(Duration::from_secs(3600)).await;
+}
+
+/// Layers CLI flags over `Config::from_env`, so an explicit flag always wins
+/// and an unset flag falls through to the env var / built-in default.
+fn resolve_config(cli: Cli) -> Config {
+    let mut config = Config::from_env();
+    if let Some(listen) = cli.listen { config.listen = listen; }
+    if let Some(account_equity) = cli.account_equity { config.account_equity = account_equity; }
+    if let Some(risk_fraction) = cli.risk_fraction { config.risk_fraction = risk_fraction; }
+    if let Some(default_symbol) = cli.default_symbol { config.default_symbol = default_symbol; }
+    if let Some(rsi_period) = cli.rsi_period { config.rsi_period = rsi_period; }
+    config
+}
+
+#[cfg(test)]
+mod config_resolution_tests {
+    use super::*;
+
+    fn empty_cli() -> Cli {
+        Cli { listen: None, account_equity: None, risk_fraction: None, default_symbol: None, rsi_period: None }
+    }
+
+    #[test]
+    fn risk_fraction_precedence_is_cli_then_env_then_default() {
+        // All three precedence levels live in one test so they share a single
+        // critical section over the shared-process FKS_RISK_FRACTION var,
+        // rather than racing against other #[test] fns that might set it.
+        std::env::remove_var("FKS_RISK_FRACTION");
+        assert_eq!(resolve_config(empty_cli()).risk_fraction, config::DEFAULT_RISK_FRACTION);
+
+        std::env::set_var("FKS_RISK_FRACTION", "0.02");
+        assert_eq!(resolve_config(empty_cli()).risk_fraction, 0.02, "unset CLI flag should fall through to env");
+
+        let cli = Cli { risk_fraction: Some(0.05), ..empty_cli() };
+        assert_eq!(resolve_config(cli).risk_fraction, 0.05, "set CLI flag should win over env");
+
+        std::env::remove_var("FKS_RISK_FRACTION");
+    }
+
+    #[test]
+    fn listen_precedence_is_cli_then_env_then_default() {
+        std::env::remove_var("FKS_LISTEN");
+        assert_eq!(resolve_config(empty_cli()).listen, config::DEFAULT_LISTEN);
+
+        std::env::set_var("FKS_LISTEN", "0.0.0.0:9999");
+        assert_eq!(resolve_config(empty_cli()).listen, "0.0.0.0:9999");
+
+        let cli = Cli { listen: Some("127.0.0.1:4000".to_string()), ..empty_cli() };
+        assert_eq!(resolve_config(cli).listen, "127.0.0.1:4000");
+
+        std::env::remove_var("FKS_LISTEN");
     }
 }
 
@@ -75,30 +205,179 @@ async fn shutdown_signal() {
     tokio::select! { _ = ctrl_c => {}, _ = terminate => {} }
 }
 
-async fn get_signal_handler() -> Json<Signal> {
-    build_signal(None).await
+type SignalResult = Result<Json<Signal>, (StatusCode, Json<ErrorBody>)>;
+
+async fn get_signal_handler(State(state): State<Arc<AppState>>) -> SignalResult {
+    build_signal(&state, SignalRequest { symbol: None, prices: None, rsi_period: None, ema_period: None, include_macd: None, include_bollinger: None }).await
+}
+
+async fn post_signal_handler(State(state): State<Arc<AppState>>, Json(req): Json<SignalRequest>) -> SignalResult {
+    let Json(signal) = build_signal(&state, req).await?;
+    // Best-effort: no subscribers is not an error, so ignore the send result.
+    let _ = state.signal_tx.send(SignalEvent { symbol: signal.symbol.clone(), signal: signal.clone() });
+    Ok(Json(signal))
 }
 
-async fn post_signal_handler(Json(req): Json<SignalRequest>) -> Json<Signal> {
-    let symbol = req.symbol.clone();
-    let prices = req.prices.clone();
-    build_signal(symbol.zip(prices)).await
+/// Built-in fallback series used when a caller doesn't supply both a symbol
+/// and non-empty prices (supplied prices without a symbol are ignored) and
+/// `FKS_DEFAULT_PRICES` isn't set, long enough to satisfy the default
+/// RSI/EMA/MACD/Bollinger periods.
+fn builtin_default_prices() -> Vec<f64> {
+    let base = 4420.0;
+    (0..40).map(|i| base + (i as f64 * 0.37).sin() * 6.0 + i as f64 * 0.05).collect()
 }
 
-async fn build_signal(input: Option<(String, Vec<f64>)>) -> Json<Signal> {
+async fn build_signal(state: &AppState, req: SignalRequest) -> SignalResult {
     let start = Instant::now();
-    let (symbol, prices) = match input {
-        Some((sym, p)) if !p.is_empty() => (sym, p),
-        _ => ("ES".to_string(), vec![4420.0, 4422.0, 4419.5, 4425.0, 4424.0])
+    let (symbol, prices) = match (req.symbol, req.prices) {
+        (Some(sym), Some(p)) if !p.is_empty() => (sym, p),
+        _ => (
+            state.config.default_symbol.clone(),
+            state.config.default_prices.clone().unwrap_or_else(builtin_default_prices),
+        ),
+    };
+    let rsi_period = req.rsi_period.unwrap_or(state.config.rsi_period);
+    let ema_period = req.ema_period.unwrap_or(DEFAULT_EMA_PERIOD);
+    let rsi = indicators::rsi(&prices, rsi_period).map_err(to_422)?;
+    let ema = indicators::ema(&prices, ema_period).map_err(to_422)?;
+    let macd = if req.include_macd.unwrap_or(false) {
+        Some(indicators::macd(&prices, DEFAULT_MACD_FAST, DEFAULT_MACD_SLOW, DEFAULT_MACD_SIGNAL).map_err(to_422)?)
+    } else {
+        None
     };
-    let rsi = 55.0; // placeholder
-    let ema: f64 = prices.iter().sum::<f64>() / prices.len() as f64;
-    let risk_allowance = 150000.0 * 0.01;
+    let bollinger = if req.include_bollinger.unwrap_or(false) {
+        Some(indicators::bollinger_bands(&prices, DEFAULT_BOLLINGER_PERIOD, DEFAULT_BOLLINGER_STD_DEV).map_err(to_422)?)
+    } else {
+        None
+    };
+    let risk_allowance = state.config.account_equity * state.config.risk_fraction;
     tokio::time::sleep(Duration::from_millis(5)).await;
-    Json(Signal { symbol, rsi, ema, risk_allowance, latency_ms: start.elapsed().as_millis() })
+    Ok(Json(Signal { symbol, rsi, ema, risk_allowance, latency_ms: start.elapsed().as_millis(), macd, bollinger }))
+}
+
+fn to_422(e: indicators::IndicatorError) -> (StatusCode, Json<ErrorBody>) {
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(ErrorBody { error: e.to_string() }))
+}
+
+#[derive(Deserialize)]
+struct OrderRequest {
+    symbol: String,
+    rsi: f64,
+    entry_price: f64,
+    tick_value: f64,
+    stop_distance_ticks: f64,
+    tick_size: Option<f64>,
+    equity: Option<f64>,
+    risk_fraction: Option<f64>,
+    reward_risk_multiple: Option<f64>,
+    max_contracts: Option<u64>,
+    neutral_rsi_lower: Option<f64>,
+    neutral_rsi_upper: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct OrderResponse {
+    symbol: String,
+    #[serde(flatten)]
+    position: SizedPosition,
+}
+
+/// `POST /execute/order`: sizes a position from a symbol's already-computed
+/// RSI and the account's risk parameters, rejecting (422) neutral signals
+/// and sizes that round to zero contracts.
+async fn post_order_handler(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<OrderRequest>,
+) -> Result<Json<OrderResponse>, (StatusCode, Json<ErrorBody>)> {
+    let input = risk::SizingInput {
+        rsi: req.rsi,
+        entry_price: req.entry_price,
+        equity: req.equity.unwrap_or(state.config.account_equity),
+        risk_fraction: req.risk_fraction.unwrap_or(state.config.risk_fraction),
+        tick_value: req.tick_value,
+        tick_size: req.tick_size.unwrap_or(1.0),
+        stop_distance_ticks: req.stop_distance_ticks,
+        reward_risk_multiple: req.reward_risk_multiple.unwrap_or(state.config.reward_risk_multiple),
+        // `max_contracts` in the request can only tighten the configured
+        // ceiling, never raise it.
+        max_contracts: req.max_contracts.map_or(state.config.max_contracts, |v| v.min(state.config.max_contracts)),
+        neutral_rsi_lower: req.neutral_rsi_lower.unwrap_or(state.config.neutral_rsi_lower),
+        neutral_rsi_upper: req.neutral_rsi_upper.unwrap_or(state.config.neutral_rsi_upper),
+    };
+    let position = risk::size_position(&input)
+        .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, Json(ErrorBody { error: e.to_string() })))?;
+    Ok(Json(OrderResponse { symbol: req.symbol, position }))
+}
+
+#[derive(Deserialize)]
+struct StreamParams { symbol: Option<String> }
+
+/// `GET /execute/stream`: subscribes to the broadcast channel fed by
+/// `POST /execute/signal` and streams matching signals as SSE `data:` events,
+/// with axum's default keep-alive comments between them.
+async fn stream_handler(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<StreamParams>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let rx = state.signal_tx.subscribe();
+    let stream = BroadcastStream::new(rx).filter_map(move |msg| {
+        let wanted = params.symbol.clone();
+        async move {
+            let event = match msg {
+                Ok(event) => event,
+                Err(_) => return None, // receiver lagged; skip the gap rather than erroring
+            };
+            if wanted.as_deref().is_some_and(|s| s != event.symbol) {
+                return None;
+            }
+            let json = serde_json::to_string(&event.signal).ok()?;
+            Some(Ok(Event::default().event("signal").data(json)))
+        }
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
 }
 
 async fn health_handler(State(state): State<Arc<AppState>>) -> Json<Health> {
     let uptime = state.start.elapsed().as_secs();
     Json(Health { service: format!("fks-execution|uptime={uptime}s"), status: "healthy".into() })
 }
+
+/// `GET /ready`: runs real checks (indicator engine, configured upstream
+/// market-data sources, broadcast-subscriber count) and returns 503 if any
+/// fail, so orchestrators can tell "process up" from "ready to serve
+/// signals".
+async fn ready_handler(State(state): State<Arc<AppState>>) -> (StatusCode, Json<Readiness>) {
+    let indicator_check = match indicators::rsi(&READINESS_CANARY_PRICES, READINESS_RSI_PERIOD) {
+        Ok(_) => CheckResult::pass("indicator_engine", None),
+        Err(e) => CheckResult::fail("indicator_engine", e.to_string()),
+    };
+    let market_data_check = check_market_data(&state.config.market_data_sources).await;
+    let subscriber_check = CheckResult::pass(
+        "broadcast_subscribers",
+        Some(format!("{} active", state.signal_tx.receiver_count())),
+    );
+    let readiness = Readiness::from_checks(vec![indicator_check, market_data_check, subscriber_check]);
+    let status = if readiness.all_pass() { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status, Json(readiness))
+}
+
+/// Dials each configured `host:port` concurrently with a shared 2s timeout
+/// per dial, so `/ready` latency is bounded regardless of how many sources
+/// are down; an empty list is treated as a trivial pass since no sources are
+/// wired up yet.
+async fn check_market_data(sources: &[String]) -> CheckResult {
+    if sources.is_empty() {
+        return CheckResult::pass("market_data_sources", Some("none configured".into()));
+    }
+    let dials = sources.iter().map(|addr| async move {
+        match tokio::time::timeout(Duration::from_secs(2), tokio::net::TcpStream::connect(addr)).await {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(e)) => Err(format!("{addr}: {e}")),
+            Err(_) => Err(format!("{addr}: timed out")),
+        }
+    });
+    match join_all(dials).await.into_iter().find_map(Result::err) {
+        Some(reason) => CheckResult::fail("market_data_sources", reason),
+        None => CheckResult::pass("market_data_sources", Some(format!("{} reachable", sources.len()))),
+    }
+}
@@ -0,0 +1,191 @@
+//! Converts a signal plus account risk parameters into an order an execution
+//! venue could actually place: a contract count, stop, target and notional
+//! exposure. Kept free of axum/HTTP types so it can be unit tested in
+//! isolation from the request plumbing in `main.rs`.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy)]
+pub enum SizingError {
+    NeutralSignal { rsi: f64, lower_band: f64, upper_band: f64 },
+    ZeroSize,
+    NonPositiveInput { field: &'static str, value: f64 },
+}
+
+impl std::fmt::Display for SizingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SizingError::NeutralSignal { rsi, lower_band, upper_band } => {
+                write!(f, "signal is neutral: rsi {rsi:.2} within [{lower_band:.2}, {upper_band:.2}]")
+            }
+            SizingError::ZeroSize => write!(f, "sized position rounds to zero contracts"),
+            SizingError::NonPositiveInput { field, value } => {
+                write!(f, "{field} must be positive, got {value}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SizingError {}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    Long,
+    Short,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SizedPosition {
+    pub direction: Direction,
+    pub contracts: u64,
+    pub risk_dollars: f64,
+    pub stop_price: f64,
+    pub target_price: f64,
+    pub notional_exposure: f64,
+}
+
+/// Everything needed to size one order. `tick_size` is the price increment
+/// represented by one tick (e.g. 0.25 for ES); `tick_value` is its dollar
+/// value, so `tick_value / tick_size` converts a price move into dollars.
+pub struct SizingInput {
+    pub rsi: f64,
+    pub entry_price: f64,
+    pub equity: f64,
+    pub risk_fraction: f64,
+    pub tick_value: f64,
+    pub tick_size: f64,
+    pub stop_distance_ticks: f64,
+    pub reward_risk_multiple: f64,
+    pub max_contracts: u64,
+    pub neutral_rsi_lower: f64,
+    pub neutral_rsi_upper: f64,
+}
+
+/// `risk_dollars = equity * risk_fraction`,
+/// `contracts = floor(risk_dollars / (stop_distance_ticks * tick_value))`,
+/// clamped to `max_contracts` and rejected when the signal is neutral (RSI
+/// inside the configured bands), `stop_distance_ticks`/`tick_value`/
+/// `tick_size` aren't positive, or the sized count rounds to zero.
+pub fn size_position(input: &SizingInput) -> Result<SizedPosition, SizingError> {
+    if input.stop_distance_ticks <= 0.0 {
+        return Err(SizingError::NonPositiveInput { field: "stop_distance_ticks", value: input.stop_distance_ticks });
+    }
+    if input.tick_value <= 0.0 {
+        return Err(SizingError::NonPositiveInput { field: "tick_value", value: input.tick_value });
+    }
+    if input.tick_size <= 0.0 {
+        return Err(SizingError::NonPositiveInput { field: "tick_size", value: input.tick_size });
+    }
+    if input.rsi > input.neutral_rsi_lower && input.rsi < input.neutral_rsi_upper {
+        return Err(SizingError::NeutralSignal {
+            rsi: input.rsi,
+            lower_band: input.neutral_rsi_lower,
+            upper_band: input.neutral_rsi_upper,
+        });
+    }
+    let direction = if input.rsi >= input.neutral_rsi_upper { Direction::Long } else { Direction::Short };
+
+    let risk_dollars = input.equity * input.risk_fraction;
+    let risk_per_contract = input.stop_distance_ticks * input.tick_value;
+    let contracts = (risk_dollars / risk_per_contract).floor().max(0.0) as u64;
+    let contracts = contracts.min(input.max_contracts);
+    if contracts == 0 {
+        return Err(SizingError::ZeroSize);
+    }
+
+    let stop_distance_price = input.stop_distance_ticks * input.tick_size;
+    let target_distance_price = stop_distance_price * input.reward_risk_multiple;
+    let (stop_price, target_price) = match direction {
+        Direction::Long => (input.entry_price - stop_distance_price, input.entry_price + target_distance_price),
+        Direction::Short => (input.entry_price + stop_distance_price, input.entry_price - target_distance_price),
+    };
+
+    let point_value = input.tick_value / input.tick_size;
+    let notional_exposure = contracts as f64 * input.entry_price * point_value;
+
+    Ok(SizedPosition { direction, contracts, risk_dollars, stop_price, target_price, notional_exposure })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_input() -> SizingInput {
+        SizingInput {
+            rsi: 70.0,
+            entry_price: 4420.0,
+            equity: 150_000.0,
+            risk_fraction: 0.01,
+            tick_value: 12.50,
+            tick_size: 0.25,
+            stop_distance_ticks: 8.0,
+            reward_risk_multiple: 2.0,
+            max_contracts: 10,
+            neutral_rsi_lower: 45.0,
+            neutral_rsi_upper: 55.0,
+        }
+    }
+
+    #[test]
+    fn neutral_band_boundary_is_exclusive() {
+        // Exactly on a band edge is directional, not neutral.
+        let at_upper_edge = SizingInput { rsi: 55.0, ..base_input() };
+        assert!(size_position(&at_upper_edge).is_ok());
+        let at_lower_edge = SizingInput { rsi: 45.0, ..base_input() };
+        assert!(size_position(&at_lower_edge).is_ok());
+    }
+
+    #[test]
+    fn inside_neutral_band_is_rejected() {
+        let input = SizingInput { rsi: 50.0, ..base_input() };
+        let err = size_position(&input).unwrap_err();
+        assert!(matches!(err, SizingError::NeutralSignal { .. }));
+    }
+
+    #[test]
+    fn zero_stop_distance_is_rejected_not_infinite_contracts() {
+        let input = SizingInput { stop_distance_ticks: 0.0, ..base_input() };
+        let err = size_position(&input).unwrap_err();
+        assert!(matches!(err, SizingError::NonPositiveInput { field: "stop_distance_ticks", .. }));
+    }
+
+    #[test]
+    fn negative_tick_value_is_rejected() {
+        let input = SizingInput { tick_value: -12.50, ..base_input() };
+        let err = size_position(&input).unwrap_err();
+        assert!(matches!(err, SizingError::NonPositiveInput { field: "tick_value", .. }));
+    }
+
+    #[test]
+    fn max_contracts_clamps_the_sized_count() {
+        let input = SizingInput { max_contracts: 1, ..base_input() };
+        let position = size_position(&input).unwrap();
+        assert_eq!(position.contracts, 1);
+    }
+
+    #[test]
+    fn tiny_equity_rounds_to_zero_and_is_rejected() {
+        let input = SizingInput { equity: 1.0, ..base_input() };
+        let err = size_position(&input).unwrap_err();
+        assert!(matches!(err, SizingError::ZeroSize));
+    }
+
+    #[test]
+    fn long_signal_places_stop_below_and_target_above_entry() {
+        let input = SizingInput { rsi: 70.0, ..base_input() };
+        let position = size_position(&input).unwrap();
+        assert_eq!(position.direction, Direction::Long);
+        assert!(position.stop_price < input.entry_price);
+        assert!(position.target_price > input.entry_price);
+    }
+
+    #[test]
+    fn short_signal_places_stop_above_and_target_below_entry() {
+        let input = SizingInput { rsi: 30.0, ..base_input() };
+        let position = size_position(&input).unwrap();
+        assert_eq!(position.direction, Direction::Short);
+        assert!(position.stop_price > input.entry_price);
+        assert!(position.target_price < input.entry_price);
+    }
+}